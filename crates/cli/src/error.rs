@@ -1,11 +1,17 @@
 use std::{
     fmt::{Debug, Display},
-    io,
+    fs, io,
     path::PathBuf,
 };
 
 use aiken_lang::{error::ParseError, tipo};
 use miette::{Diagnostic, EyreContext, LabeledSpan, MietteHandlerOpts, RgbColors, SourceCode};
+use uplc::{
+    ast::{Name, NamedDeBruijn, Program},
+    flat::DecodeLimits,
+    flat_error::FlatDecodeError,
+    parser::ParserError,
+};
 
 #[allow(dead_code)]
 #[derive(thiserror::Error)]
@@ -44,6 +50,22 @@ pub enum Error {
         #[source]
         error: tipo::error::Error,
     },
+
+    #[error("invalid untyped plutus core")]
+    Uplc {
+        path: PathBuf,
+        src: String,
+        #[source]
+        error: ParserError,
+    },
+
+    #[error("malformed flat-encoded plutus core")]
+    FlatDecode {
+        path: PathBuf,
+        src: Vec<u8>,
+        #[source]
+        error: FlatDecodeError,
+    },
 }
 
 impl Error {
@@ -64,6 +86,31 @@ impl Error {
             rest => eprintln!("Error: {:?}", rest),
         }
     }
+
+    /// Read a flat-encoded compiled script off disk, giving a malformed file
+    /// the same [`Diagnostic`]-rendered experience as a parse or type error
+    /// instead of the bare [`FlatDecodeError`] `Program::unflat_with_limits` returns.
+    pub fn read_flat_script(path: PathBuf) -> Result<Program<NamedDeBruijn>, Error> {
+        let src = fs::read(&path).map_err(|error| Error::FileIo {
+            error,
+            path: path.clone(),
+        })?;
+
+        Program::<NamedDeBruijn>::unflat_with_limits(&src, DecodeLimits::default())
+            .map_err(|error| Error::FlatDecode { path, src, error })
+    }
+
+    /// Parse a textual UPLC source file off disk, giving a malformed file
+    /// the same [`Diagnostic`]-rendered experience as a parse or type error
+    /// instead of the bare `ParserError` `Program::assemble` returns.
+    pub fn assemble_uplc_script(path: PathBuf) -> Result<Vec<u8>, Error> {
+        let src = fs::read_to_string(&path).map_err(|error| Error::FileIo {
+            error,
+            path: path.clone(),
+        })?;
+
+        Program::<Name>::assemble(&src).map_err(|error| Error::Uplc { path, src, error })
+    }
 }
 
 impl Debug for Error {
@@ -94,6 +141,8 @@ impl Diagnostic for Error {
             Error::List(_) => None,
             Error::Parse { .. } => Some(Box::new("aiken::parser")),
             Error::Type { .. } => Some(Box::new("aiken::typecheck")),
+            Error::Uplc { .. } => Some(Box::new("aiken::uplc::parser")),
+            Error::FlatDecode { .. } => Some(Box::new("aiken::uplc::flat")),
         }
     }
 
@@ -112,6 +161,10 @@ impl Diagnostic for Error {
             Error::List(_) => None,
             Error::Parse { error, .. } => error.kind.help(),
             Error::Type { error, .. } => error.help(),
+            Error::Uplc { .. } => None,
+            Error::FlatDecode { .. } => {
+                Some(Box::new("the script bytes are corrupt or were not produced by this compiler"))
+            }
         }
     }
 
@@ -123,6 +176,17 @@ impl Diagnostic for Error {
             Error::List(_) => None,
             Error::Parse { error, .. } => error.labels(),
             Error::Type { error, .. } => error.labels(),
+            Error::Uplc { error, .. } => Some(Box::new(
+                vec![LabeledSpan::new(
+                    Some(error.kind.to_string()),
+                    error.span.start,
+                    error.span.len(),
+                )]
+                .into_iter(),
+            )),
+            Error::FlatDecode { error, .. } => Some(Box::new(
+                vec![LabeledSpan::new(Some(error.kind.to_string()), error.offset, 1)].into_iter(),
+            )),
         }
     }
 
@@ -134,6 +198,8 @@ impl Diagnostic for Error {
             Error::List(_) => None,
             Error::Parse { src, .. } => Some(src),
             Error::Type { src, .. } => Some(src),
+            Error::Uplc { src, .. } => Some(src),
+            Error::FlatDecode { src, .. } => Some(src),
         }
     }
 }
@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use uplc::{
+    ast::{Constant, Name, Program, Term, Type},
+    flat::DecodeLimits,
+};
+
+fn representative_program() -> Program<Name> {
+    Program {
+        version: (1, 0, 0),
+        term: Term::Constant(Constant::ProtoList(
+            Type::Integer,
+            (0..256).map(|n| Constant::Integer(n.into())).collect(),
+        )),
+    }
+}
+
+fn bench_flat_decode(c: &mut Criterion) {
+    let bytes = representative_program().to_flat().unwrap();
+
+    let mut group = c.benchmark_group("flat_decode");
+
+    group.bench_function("unflat", |b| {
+        b.iter(|| Program::<Name>::unflat(black_box(&bytes)).unwrap())
+    });
+
+    group.bench_function("unflat_fast", |b| {
+        b.iter(|| Program::<Name>::unflat_fast(black_box(&bytes), DecodeLimits::default()).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_flat_decode);
+criterion_main!(benches);
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+
+use crate::{builtins::DefaultFunction, data::PlutusData};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program<T> {
+    pub version: (usize, usize, usize),
+    pub term: Term<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term<T> {
+    Var(T),
+    Delay(Box<Term<T>>),
+    Lambda {
+        parameter_name: T,
+        body: Box<Term<T>>,
+    },
+    Apply {
+        function: Box<Term<T>>,
+        argument: Box<Term<T>>,
+    },
+    Constant(Constant),
+    Force(Box<Term<T>>),
+    Error,
+    Builtin(DefaultFunction),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Integer(BigInt),
+    ByteString(Vec<u8>),
+    String(String),
+    // there is no char constant tag
+    Char(char),
+    Unit,
+    Bool(bool),
+    ProtoList(Type, Vec<Constant>),
+    ProtoPair(Type, Type, Box<Constant>, Box<Constant>),
+    Data(PlutusData),
+}
+
+/// The type of a [`Constant`], used by the flat codec to tag a constant's
+/// shape ahead of its value so that lists, pairs, and their element types
+/// can be decoded structurally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Integer,
+    ByteString,
+    String,
+    Unit,
+    Bool,
+    List(Box<Type>),
+    Pair(Box<Type>, Box<Type>),
+    Data,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    pub text: String,
+    pub unique: Unique,
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.text, isize::from(self.unique))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamedDeBruijn {
+    pub text: String,
+    pub index: DeBruijn,
+}
+
+impl fmt::Display for NamedDeBruijn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unique(isize);
+
+impl From<isize> for Unique {
+    fn from(unique: isize) -> Self {
+        Unique(unique)
+    }
+}
+
+impl From<Unique> for isize {
+    fn from(unique: Unique) -> Self {
+        unique.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeBruijn(usize);
+
+impl DeBruijn {
+    pub fn new(index: usize) -> Self {
+        DeBruijn(index)
+    }
+}
+
+impl From<usize> for DeBruijn {
+    fn from(index: usize) -> Self {
+        DeBruijn(index)
+    }
+}
+
+impl From<DeBruijn> for usize {
+    fn from(index: DeBruijn) -> Self {
+        index.0
+    }
+}
+
+impl fmt::Display for DeBruijn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,131 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefaultFunction {
+    // Integers
+    AddInteger,
+    SubtractInteger,
+    MultiplyInteger,
+    DivideInteger,
+    QuotientInteger,
+    RemainderInteger,
+    ModInteger,
+    EqualsInteger,
+    LessThanInteger,
+    LessThanEqualsInteger,
+    // ByteStrings
+    AppendByteString,
+    ConsByteString,
+    SliceByteString,
+    LengthOfByteString,
+    IndexByteString,
+    EqualsByteString,
+    LessThanByteString,
+    LessThanEqualsByteString,
+    // Cryptography and hashes
+    Sha2_256,
+    Sha3_256,
+    Blake2b_256,
+    VerifyEd25519Signature,
+    // Strings
+    AppendString,
+    EqualsString,
+    EncodeUtf8,
+    DecodeUtf8,
+    // Bool
+    IfThenElse,
+    // Unit
+    ChooseUnit,
+    // Tracing
+    Trace,
+    // Pairs
+    FstPair,
+    SndPair,
+    // Lists
+    ChooseList,
+    MkCons,
+    HeadList,
+    TailList,
+    NullList,
+    // Data
+    ChooseData,
+    ConstrData,
+    MapData,
+    ListData,
+    IData,
+    BData,
+    UnConstrData,
+    UnMapData,
+    UnListData,
+    UnIData,
+    UnBData,
+    EqualsData,
+    MkPairData,
+    MkNilData,
+    MkNilPairData,
+    SerialiseData,
+}
+
+impl TryFrom<u8> for DefaultFunction {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use DefaultFunction::*;
+
+        let builtin = match value {
+            0 => AddInteger,
+            1 => SubtractInteger,
+            2 => MultiplyInteger,
+            3 => DivideInteger,
+            4 => QuotientInteger,
+            5 => RemainderInteger,
+            6 => ModInteger,
+            7 => EqualsInteger,
+            8 => LessThanInteger,
+            9 => LessThanEqualsInteger,
+            10 => AppendByteString,
+            11 => ConsByteString,
+            12 => SliceByteString,
+            13 => LengthOfByteString,
+            14 => IndexByteString,
+            15 => EqualsByteString,
+            16 => LessThanByteString,
+            17 => LessThanEqualsByteString,
+            18 => Sha2_256,
+            19 => Sha3_256,
+            20 => Blake2b_256,
+            21 => VerifyEd25519Signature,
+            22 => AppendString,
+            23 => EqualsString,
+            24 => EncodeUtf8,
+            25 => DecodeUtf8,
+            26 => IfThenElse,
+            27 => ChooseUnit,
+            28 => Trace,
+            29 => FstPair,
+            30 => SndPair,
+            31 => ChooseList,
+            32 => MkCons,
+            33 => HeadList,
+            34 => TailList,
+            35 => NullList,
+            36 => ChooseData,
+            37 => ConstrData,
+            38 => MapData,
+            39 => ListData,
+            40 => IData,
+            41 => BData,
+            42 => UnConstrData,
+            43 => UnMapData,
+            44 => UnListData,
+            45 => UnIData,
+            46 => UnBData,
+            47 => EqualsData,
+            48 => MkPairData,
+            49 => MkNilData,
+            50 => MkNilPairData,
+            51 => SerialiseData,
+            x => return Err(format!("Unknown builtin function tag: {x}")),
+        };
+
+        Ok(builtin)
+    }
+}
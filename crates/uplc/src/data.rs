@@ -0,0 +1,13 @@
+use num_bigint::BigInt;
+
+/// The Plutus `Data` type: a CBOR-like, untyped representation used for
+/// datums and redeemers so that on-chain values don't need to carry a
+/// statically known Plutus Core type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlutusData {
+    Constr { tag: usize, fields: Vec<PlutusData> },
+    Map(Vec<(PlutusData, PlutusData)>),
+    List(Vec<PlutusData>),
+    I(BigInt),
+    B(Vec<u8>),
+}
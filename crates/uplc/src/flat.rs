@@ -5,15 +5,45 @@ use flat::{
     en::{Encode, Encoder},
     Flat,
 };
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Zero;
 
 use crate::{
-    ast::{Constant, DeBruijn, Name, NamedDeBruijn, Program, Term, Unique},
+    ast::{Constant, DeBruijn, Name, NamedDeBruijn, Program, Term, Type, Unique},
     builtins::DefaultFunction,
+    data::PlutusData,
+    flat_error::{FlatDecodeError, FlatDecodeErrorKind},
 };
 
 const BUILTIN_TAG_WIDTH: u32 = 7;
 const CONST_TAG_WIDTH: u32 = 4;
 const TERM_TAG_WIDTH: u32 = 4;
+const DATA_TAG_WIDTH: u32 = 3;
+
+/// The decoder's current position in the input, in bytes, used to attach a
+/// byte offset to a [`FlatDecodeError`] so it can point at the offending
+/// span of a malformed flat blob.
+fn offset(d: &Decoder) -> usize {
+    d.pos
+}
+
+fn eof_at(d: &Decoder) -> FlatDecodeError {
+    FlatDecodeError {
+        kind: FlatDecodeErrorKind::UnexpectedEof,
+        offset: offset(d),
+    }
+}
+
+/// Wraps a sub-decoder's own failure message (a [`Binder`] or
+/// [`DefaultFunction`](crate::builtins::DefaultFunction) decode, which
+/// returns a bare `String`) instead of collapsing it to `UnexpectedEof`,
+/// which would mislabel e.g. invalid UTF-8 in a variable name.
+fn invalid_at(d: &Decoder, msg: String) -> FlatDecodeError {
+    FlatDecodeError {
+        kind: FlatDecodeErrorKind::Invalid(msg),
+        offset: offset(d),
+    }
+}
 
 pub trait Binder<'b>: Encode + Decode<'b> {
     fn binder_encode(&self, e: &mut Encoder) -> Result<(), String>;
@@ -41,6 +71,129 @@ where
     }
 }
 
+/// Resource bounds enforced by [`Program::unflat_with_limits`] while
+/// decoding a flat-encoded program, so an untrusted or adversarial blob
+/// (deeply nested applications, a giant bytestring, an unbounded list) is
+/// rejected with a [`FlatDecodeError`] instead of exhausting memory or the
+/// stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Total number of input bytes that may be consumed.
+    pub max_bytes: usize,
+    /// Maximum nesting depth of `Delay`/`Force`/`Lambda`/`Apply` terms, and,
+    /// counted separately within each constant, of its `list`/`pair` type
+    /// tags, its list and pair values, and its `Data` values.
+    pub max_depth: usize,
+    /// Maximum byte length of a single integer, bytestring, or string constant.
+    pub max_constant_bytes: usize,
+    /// Maximum number of elements in a single flat-encoded list.
+    pub max_list_len: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_bytes: 16 * 1024 * 1024,
+            max_depth: 512,
+            max_constant_bytes: 4 * 1024 * 1024,
+            max_list_len: 1_024 * 1_024,
+        }
+    }
+}
+
+impl<'b, T> Program<T>
+where
+    T: Binder<'b>,
+{
+    /// Decode a flat-encoded program like [`Flat::unflat`], but enforcing
+    /// `limits` along the way so a corrupt or adversarial blob is rejected
+    /// with a structured [`FlatDecodeError`] the moment a bound is
+    /// exceeded, rather than after allocating on its behalf.
+    pub fn unflat_with_limits(bytes: &'b [u8], limits: DecodeLimits) -> Result<Self, FlatDecodeError> {
+        if bytes.len() > limits.max_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::InputTooLarge,
+                offset: 0,
+            });
+        }
+
+        let mut d = Decoder::new(bytes);
+
+        let version = (
+            decode_usize_with_limits(&mut d, &limits)?,
+            decode_usize_with_limits(&mut d, &limits)?,
+            decode_usize_with_limits(&mut d, &limits)?,
+        );
+
+        let term = decode_term_with_limits::<T>(&mut d, &limits, 0)?;
+
+        Ok(Program { version, term })
+    }
+
+    /// Decode a flat-encoded program for hot paths that decode many
+    /// scripts: make a single cheap structural pass over `bytes` that
+    /// enforces `limits` without materializing any `Term`/`Constant`/`BigInt`
+    /// (see [`validate_term`]), then re-read it through an infallible
+    /// decoder that trusts that pass instead of propagating a `Result` out
+    /// of every primitive read.
+    pub fn unflat_fast(bytes: &'b [u8], limits: DecodeLimits) -> Result<Self, FlatDecodeError> {
+        if bytes.len() > limits.max_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::InputTooLarge,
+                offset: 0,
+            });
+        }
+
+        let mut validator = Decoder::new(bytes);
+
+        decode_usize_with_limits(&mut validator, &limits)?;
+        decode_usize_with_limits(&mut validator, &limits)?;
+        decode_usize_with_limits(&mut validator, &limits)?;
+
+        validate_term::<T>(&mut validator, &limits, 0)?;
+
+        let mut d = Decoder::new(bytes);
+
+        let version = (
+            usize::decode(&mut d).expect("validated above"),
+            usize::decode(&mut d).expect("validated above"),
+            usize::decode(&mut d).expect("validated above"),
+        );
+
+        let term = decode_term_unchecked::<T>(&mut d);
+
+        Ok(Program { version, term })
+    }
+}
+
+impl Program<Name> {
+    /// Disassemble a flat-encoded program into the textual UPLC surface
+    /// syntax that [`Program::assemble`] reads back. Constants, builtins and
+    /// the term structure survive that round trip unchanged; a variable
+    /// printed as `<text>_<unique>` is read back with that whole word as
+    /// its text and a fresh unique, so a program with variables does not
+    /// reassemble to the same bytes.
+    pub fn disassemble(bytes: &[u8]) -> anyhow::Result<String> {
+        let program: Program<Name> = Program::unflat(bytes).map_err(|err| anyhow!("{}", err))?;
+
+        Ok(program.to_string())
+    }
+
+    /// Assemble the textual UPLC surface syntax into a flat-encoded program;
+    /// see [`Program::disassemble`] for what a round trip through the text
+    /// keeps. Returns the parser's own
+    /// [`ParserError`](crate::parser::ParserError), with its span intact,
+    /// rather than collapsing it into an opaque `anyhow::Error` the way
+    /// [`Program::to_flat`] does for the (infallible in practice) encode step.
+    pub fn assemble(source: &str) -> Result<Vec<u8>, crate::parser::ParserError> {
+        let program = crate::parser::parse(source)?;
+
+        Ok(program
+            .to_flat()
+            .expect("encoding a successfully parsed program never fails"))
+    }
+}
+
 impl<'b, T> Encode for Program<T>
 where
     T: Binder<'b>,
@@ -142,54 +295,363 @@ where
             5 => Ok(Term::Force(Box::new(Term::decode(d)?))),
             6 => Ok(Term::Error),
             7 => Ok(Term::Builtin(DefaultFunction::decode(d)?)),
-            x => Err(format!("Unknown term constructor tag: {}", x)),
+            x => Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::UnknownTermTag(x),
+                offset: offset(d),
+            }
+            .into()),
         }
     }
 }
 
 impl Encode for &Constant {
+    fn encode(&self, e: &mut Encoder) -> Result<(), String> {
+        // there is no char constant tag, so it skips the type/value scheme
+        // entirely and is written as a raw, untagged utf8 byte string
+        if let Constant::Char(c) = self {
+            let mut b = [0; 4];
+
+            let s = c.encode_utf8(&mut b);
+
+            return s.as_bytes().encode(e);
+        }
+
+        encode_constant_type(&constant_type(self), e)?;
+        encode_constant_value(self, e)
+    }
+}
+
+impl<'b> Decode<'b> for Constant {
+    fn decode(d: &mut Decoder) -> Result<Self, String> {
+        let typ = decode_constant_type(d)?;
+
+        decode_constant_value(&typ, d)
+    }
+}
+
+/// The [`Type`] describing a constant's shape, used to build the type tag
+/// list that precedes its value in the flat encoding.
+fn constant_type(constant: &Constant) -> Type {
+    match constant {
+        Constant::Integer(_) => Type::Integer,
+        Constant::ByteString(_) => Type::ByteString,
+        Constant::String(_) => Type::String,
+        Constant::Unit => Type::Unit,
+        Constant::Bool(_) => Type::Bool,
+        Constant::ProtoList(typ, _) => Type::List(Box::new(typ.clone())),
+        Constant::ProtoPair(a, b, _, _) => Type::Pair(Box::new(a.clone()), Box::new(b.clone())),
+        Constant::Data(_) => Type::Data,
+        // handled separately in `Encode for &Constant`
+        Constant::Char(_) => unreachable!("char constants have no type tag"),
+    }
+}
+
+fn encode_constant_value(constant: &Constant, e: &mut Encoder) -> Result<(), String> {
+    match constant {
+        Constant::Integer(i) => i.encode(e),
+        Constant::ByteString(bytes) => bytes.encode(e),
+        Constant::String(s) => s.encode(e),
+        Constant::Unit => Ok(()),
+        Constant::Bool(b) => b.encode(e),
+        Constant::ProtoList(_, items) => {
+            e.encode_list_with(|item, e| encode_constant_value(item, e), items.clone())
+        }
+        Constant::ProtoPair(_, _, a, b) => {
+            encode_constant_value(a, e)?;
+            encode_constant_value(b, e)
+        }
+        Constant::Data(data) => data.encode(e),
+        Constant::Char(_) => unreachable!("char constants have no type tag"),
+    }
+}
+
+fn decode_constant_value(typ: &Type, d: &mut Decoder) -> Result<Constant, String> {
+    match typ {
+        Type::Integer => Ok(Constant::Integer(BigInt::decode(d)?)),
+        Type::ByteString => Ok(Constant::ByteString(Vec::<u8>::decode(d)?)),
+        Type::String => Ok(Constant::String(String::decode(d)?)),
+        Type::Unit => Ok(Constant::Unit),
+        Type::Bool => Ok(Constant::Bool(bool::decode(d)?)),
+        Type::List(elem) => {
+            let items = d.decode_list_with(|d| decode_constant_value(elem, d))?;
+
+            Ok(Constant::ProtoList(elem.as_ref().clone(), items))
+        }
+        Type::Pair(a, b) => {
+            let left = decode_constant_value(a, d)?;
+            let right = decode_constant_value(b, d)?;
+
+            Ok(Constant::ProtoPair(
+                a.as_ref().clone(),
+                b.as_ref().clone(),
+                Box::new(left),
+                Box::new(right),
+            ))
+        }
+        Type::Data => Ok(Constant::Data(PlutusData::decode(d)?)),
+    }
+}
+
+fn encode_constant_type(typ: &Type, e: &mut Encoder) -> Result<(), String> {
+    let mut tags = vec![];
+
+    flatten_constant_type(typ, &mut tags);
+
+    e.encode_list_with(encode_constant_tag, tags)
+}
+
+fn flatten_constant_type(typ: &Type, tags: &mut Vec<u8>) {
+    match typ {
+        Type::Integer => tags.push(0),
+        Type::ByteString => tags.push(1),
+        Type::String => tags.push(2),
+        Type::Unit => tags.push(3),
+        Type::Bool => tags.push(4),
+        Type::List(elem) => {
+            tags.push(5);
+            flatten_constant_type(elem, tags);
+        }
+        Type::Pair(a, b) => {
+            tags.push(6);
+            flatten_constant_type(a, tags);
+            flatten_constant_type(b, tags);
+        }
+        Type::Data => tags.push(7),
+    }
+}
+
+fn decode_constant_type(d: &mut Decoder) -> Result<Type, String> {
+    let tags = d.decode_list_with(decode_constant_tag)?;
+    let constant_tag_list_end = offset(d);
+    let mut tags = tags.into_iter();
+
+    let typ = decode_constant_type_tags(&mut tags, constant_tag_list_end)?;
+
+    if tags.next().is_some() {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::MalformedConstantTagList,
+            offset: constant_tag_list_end,
+        }
+        .into());
+    }
+
+    Ok(typ)
+}
+
+fn decode_constant_type_tags(
+    tags: &mut impl Iterator<Item = u8>,
+    offset: usize,
+) -> Result<Type, FlatDecodeError> {
+    match tags.next() {
+        Some(0) => Ok(Type::Integer),
+        Some(1) => Ok(Type::ByteString),
+        Some(2) => Ok(Type::String),
+        Some(3) => Ok(Type::Unit),
+        Some(4) => Ok(Type::Bool),
+        Some(5) => Ok(Type::List(Box::new(decode_constant_type_tags(tags, offset)?))),
+        Some(6) => {
+            let a = decode_constant_type_tags(tags, offset)?;
+            let b = decode_constant_type_tags(tags, offset)?;
+
+            Ok(Type::Pair(Box::new(a), Box::new(b)))
+        }
+        Some(7) => Ok(Type::Data),
+        Some(x) => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownConstantTag(x),
+            offset,
+        }),
+        None => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::MalformedConstantTagList,
+            offset,
+        }),
+    }
+}
+
+// The limit-enforcing counterpart of `decode_constant_type_tags`: a type
+// tag list nests once per `list`/`pair` tag with no length prefix of its
+// own (only `decode_list_bounded`'s `max_list_len` cap bounds how many tags
+// there can be), so without its own depth counter a crafted tag list can
+// still drive native recursion deep enough to overflow the stack despite
+// being well under `max_bytes`.
+fn decode_constant_type_tags_bounded(
+    tags: &mut impl Iterator<Item = u8>,
+    offset: usize,
+    limits: &DecodeLimits,
+    depth: usize,
+) -> Result<Type, FlatDecodeError> {
+    match tags.next() {
+        Some(0) => Ok(Type::Integer),
+        Some(1) => Ok(Type::ByteString),
+        Some(2) => Ok(Type::String),
+        Some(3) => Ok(Type::Unit),
+        Some(4) => Ok(Type::Bool),
+        Some(5) => {
+            let depth = enter_depth_at(depth, limits, offset)?;
+
+            Ok(Type::List(Box::new(decode_constant_type_tags_bounded(
+                tags, offset, limits, depth,
+            )?)))
+        }
+        Some(6) => {
+            let depth = enter_depth_at(depth, limits, offset)?;
+
+            let a = decode_constant_type_tags_bounded(tags, offset, limits, depth)?;
+            let b = decode_constant_type_tags_bounded(tags, offset, limits, depth)?;
+
+            Ok(Type::Pair(Box::new(a), Box::new(b)))
+        }
+        Some(7) => Ok(Type::Data),
+        Some(x) => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownConstantTag(x),
+            offset,
+        }),
+        None => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::MalformedConstantTagList,
+            offset,
+        }),
+    }
+}
+
+impl Encode for &PlutusData {
     fn encode(&self, e: &mut Encoder) -> Result<(), String> {
         match self {
-            Constant::Integer(i) => {
-                encode_constant(0, e)?;
-                i.encode(e)?;
+            PlutusData::Constr { tag, fields } => {
+                safe_encode_bits(DATA_TAG_WIDTH, 0, e)?;
+                (*tag as isize).encode(e)?;
+                e.encode_list_with(|field: PlutusData, e| field.encode(e), fields.clone())
             }
-            Constant::ByteString(bytes) => {
-                encode_constant(1, e)?;
-                bytes.encode(e)?;
+            PlutusData::Map(pairs) => {
+                safe_encode_bits(DATA_TAG_WIDTH, 1, e)?;
+                e.encode_list_with(
+                    |(k, v): (PlutusData, PlutusData), e| {
+                        k.encode(e)?;
+                        v.encode(e)
+                    },
+                    pairs.clone(),
+                )
             }
-            Constant::String(s) => {
-                encode_constant(2, e)?;
-                s.encode(e)?;
+            PlutusData::List(items) => {
+                safe_encode_bits(DATA_TAG_WIDTH, 2, e)?;
+                e.encode_list_with(|item: PlutusData, e| item.encode(e), items.clone())
             }
-            // there is no char constant tag
-            Constant::Char(c) => {
-                let mut b = [0; 4];
-
-                let s = c.encode_utf8(&mut b);
-
-                s.as_bytes().encode(e)?;
+            PlutusData::I(i) => {
+                safe_encode_bits(DATA_TAG_WIDTH, 3, e)?;
+                i.encode(e)
             }
-            Constant::Unit => encode_constant(3, e)?,
-            Constant::Bool(b) => {
-                encode_constant(4, e)?;
-                b.encode(e)?;
+            PlutusData::B(bytes) => {
+                safe_encode_bits(DATA_TAG_WIDTH, 4, e)?;
+                bytes.encode(e)
             }
         }
+    }
+}
 
-        Ok(())
+// Plutus Core integers are arbitrary precision, so instead of relying on
+// the `flat` crate's fixed-width integer codecs we map to an unsigned
+// magnitude via zig-zag (so small numbers of either sign stay short) and
+// then emit that magnitude as a little-endian base-128 varint: 7 payload
+// bits per byte, with the high bit set on every byte but the last.
+impl Encode for &BigInt {
+    fn encode(&self, e: &mut Encoder) -> Result<(), String> {
+        encode_varint(&zigzag_encode(self), e)
     }
 }
 
-impl<'b> Decode<'b> for Constant {
+impl<'b> Decode<'b> for BigInt {
+    fn decode(d: &mut Decoder) -> Result<Self, String> {
+        Ok(zigzag_decode(&decode_varint(d)?))
+    }
+}
+
+fn zigzag_encode(n: &BigInt) -> BigUint {
+    if n.sign() == Sign::Minus {
+        (-n * 2 - 1)
+            .to_biguint()
+            .expect("negation of a negative BigInt is non-negative")
+    } else {
+        (n * 2)
+            .to_biguint()
+            .expect("doubling a non-negative BigInt is non-negative")
+    }
+}
+
+fn zigzag_decode(n: &BigUint) -> BigInt {
+    let n = BigInt::from(n.clone());
+
+    if &n % 2 == BigInt::from(0) {
+        n / 2
+    } else {
+        -(n + 1) / 2
+    }
+}
+
+fn encode_varint(value: &BigUint, e: &mut Encoder) -> Result<(), String> {
+    let mut remaining = value.clone();
+
+    loop {
+        let byte = (&remaining & BigUint::from(0x7fu8)).iter_u32_digits().next().unwrap_or(0) as u8;
+
+        remaining >>= 7u32;
+
+        if remaining.is_zero() {
+            e.bits(8, byte);
+            break;
+        } else {
+            e.bits(8, byte | 0x80);
+        }
+    }
+
+    Ok(())
+}
+
+fn decode_varint(d: &mut Decoder) -> Result<BigUint, String> {
+    let mut magnitude = BigUint::zero();
+    let mut shift = 0u32;
+
+    loop {
+        let byte = d.bits8(8).map_err(|_| eof_at(d))?;
+
+        magnitude |= BigUint::from(byte & 0x7f) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(magnitude)
+}
+
+impl<'b> Decode<'b> for PlutusData {
     fn decode(d: &mut Decoder) -> Result<Self, String> {
-        match decode_constant(d)? {
-            0 => Ok(Constant::Integer(isize::decode(d)?)),
-            1 => Ok(Constant::ByteString(Vec::<u8>::decode(d)?)),
-            2 => Ok(Constant::String(String::decode(d)?)),
-            3 => Ok(Constant::Unit),
-            4 => Ok(Constant::Bool(bool::decode(d)?)),
-            x => Err(format!("Unknown constant constructor tag: {}", x)),
+        let tag = d.bits8(DATA_TAG_WIDTH as usize).map_err(|_| eof_at(d))?;
+
+        match tag {
+            0 => {
+                let tag = isize::decode(d)?;
+                let tag = usize::try_from(tag).map_err(|_| FlatDecodeError {
+                    kind: FlatDecodeErrorKind::IntegerOverflow,
+                    offset: offset(d),
+                })?;
+
+                Ok(PlutusData::Constr {
+                    tag,
+                    fields: d.decode_list_with(PlutusData::decode)?,
+                })
+            }
+            1 => Ok(PlutusData::Map(d.decode_list_with(|d| {
+                let k = PlutusData::decode(d)?;
+                let v = PlutusData::decode(d)?;
+                Ok((k, v))
+            })?)),
+            2 => Ok(PlutusData::List(d.decode_list_with(PlutusData::decode)?)),
+            3 => Ok(PlutusData::I(BigInt::decode(d)?)),
+            4 => Ok(PlutusData::B(Vec::<u8>::decode(d)?)),
+            x => Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::UnknownDataTag(x),
+                offset: offset(d),
+            }
+            .into()),
         }
     }
 }
@@ -315,7 +777,7 @@ fn encode_term_tag(tag: u8, e: &mut Encoder) -> Result<(), String> {
 }
 
 fn decode_term_tag(d: &mut Decoder) -> Result<u8, String> {
-    d.bits8(TERM_TAG_WIDTH as usize)
+    d.bits8(TERM_TAG_WIDTH as usize).map_err(|_| eof_at(d).into())
 }
 
 fn safe_encode_bits(num_bits: u32, byte: u8, e: &mut Encoder) -> Result<(), String> {
@@ -330,66 +792,1030 @@ fn safe_encode_bits(num_bits: u32, byte: u8, e: &mut Encoder) -> Result<(), Stri
     }
 }
 
-pub fn encode_constant(tag: u8, e: &mut Encoder) -> Result<(), String> {
-    e.encode_list_with(encode_constant_tag, [tag].to_vec())
+pub fn encode_constant_tag(tag: u8, e: &mut Encoder) -> Result<(), String> {
+    safe_encode_bits(CONST_TAG_WIDTH, tag, e)
 }
 
-pub fn decode_constant(d: &mut Decoder) -> Result<u8, String> {
-    let u8_list = d.decode_list_with(decode_constant_tag)?;
-    if u8_list.len() > 1 {
-        Err(
-            "Improper encoding on constant tag. Should be list of one item encoded in 4 bits"
-                .to_string(),
-        )
-    } else {
-        Ok(u8_list[0])
+pub fn decode_constant_tag(d: &mut Decoder) -> Result<u8, String> {
+    d.bits8(CONST_TAG_WIDTH as usize)
+        .map_err(|_| eof_at(d).into())
+}
+
+fn decode_usize_with_limits(d: &mut Decoder, _limits: &DecodeLimits) -> Result<usize, FlatDecodeError> {
+    usize::decode(d).map_err(|_| eof_at(d))
+}
+
+// A flat list is encoded as a "has another element" bit before each
+// element, terminated by a single `0` bit, rather than a length prefix -
+// so a malicious blob can claim an unbounded number of elements without
+// ever saying so up front. This mirrors `Decoder::decode_list_with`, but
+// rejects the list the moment it grows past `limits.max_list_len`.
+fn decode_list_bounded<A>(
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    mut elem: impl FnMut(&mut Decoder) -> Result<A, FlatDecodeError>,
+) -> Result<Vec<A>, FlatDecodeError> {
+    let mut items = Vec::new();
+
+    while d.bits8(1).map_err(|_| eof_at(d))? == 1 {
+        if items.len() >= limits.max_list_len {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ListTooLong,
+                offset: offset(d),
+            });
+        }
+
+        items.push(elem(d)?);
     }
+
+    Ok(items)
 }
 
-pub fn encode_constant_tag(tag: u8, e: &mut Encoder) -> Result<(), String> {
-    safe_encode_bits(CONST_TAG_WIDTH, tag, e)
+// Bytestrings are encoded as a filler (padding up to the next byte
+// boundary) followed by a sequence of up-to-255-byte chunks, each prefixed
+// by its length, terminated by a zero-length chunk.
+fn decode_bytestring_bounded(d: &mut Decoder, limits: &DecodeLimits) -> Result<Vec<u8>, FlatDecodeError> {
+    let mut bytes = Vec::new();
+
+    d.filler().map_err(|_| eof_at(d))?;
+
+    loop {
+        let chunk_len = d.bits8(8).map_err(|_| eof_at(d))? as usize;
+
+        if chunk_len == 0 {
+            break;
+        }
+
+        if bytes.len() + chunk_len > limits.max_constant_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ConstantTooLarge,
+                offset: offset(d),
+            });
+        }
+
+        for _ in 0..chunk_len {
+            bytes.push(d.bits8(8).map_err(|_| eof_at(d))?);
+        }
+    }
+
+    Ok(bytes)
 }
 
-pub fn decode_constant_tag(d: &mut Decoder) -> Result<u8, String> {
-    d.bits8(CONST_TAG_WIDTH as usize)
+fn decode_varint_bounded(d: &mut Decoder, limits: &DecodeLimits) -> Result<BigUint, FlatDecodeError> {
+    let mut magnitude = BigUint::zero();
+    let mut shift = 0u32;
+    let mut bytes_read = 0usize;
+
+    loop {
+        if bytes_read >= limits.max_constant_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ConstantTooLarge,
+                offset: offset(d),
+            });
+        }
+
+        let byte = d.bits8(8).map_err(|_| eof_at(d))?;
+        bytes_read += 1;
+
+        magnitude |= BigUint::from(byte & 0x7f) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(magnitude)
+}
+
+fn enter_depth_at(depth: usize, limits: &DecodeLimits, offset: usize) -> Result<usize, FlatDecodeError> {
+    let depth = depth + 1;
+
+    if depth > limits.max_depth {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::NestingTooDeep,
+            offset,
+        });
+    }
+
+    Ok(depth)
+}
+
+fn enter_depth(depth: usize, limits: &DecodeLimits, d: &Decoder) -> Result<usize, FlatDecodeError> {
+    enter_depth_at(depth, limits, offset(d))
+}
+
+fn decode_term_with_limits<'b, T>(
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    depth: usize,
+) -> Result<Term<T>, FlatDecodeError>
+where
+    T: Binder<'b>,
+{
+    if offset(d) > limits.max_bytes {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::InputTooLarge,
+            offset: offset(d),
+        });
+    }
+
+    let tag = d.bits8(TERM_TAG_WIDTH as usize).map_err(|_| eof_at(d))?;
+
+    match tag {
+        0 => Ok(Term::Var(T::decode(d).map_err(|msg| invalid_at(d, msg))?)),
+        1 => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            Ok(Term::Delay(Box::new(decode_term_with_limits::<T>(
+                d, limits, depth,
+            )?)))
+        }
+        2 => {
+            let depth = enter_depth(depth, limits, d)?;
+            let parameter_name = T::binder_decode(d).map_err(|msg| invalid_at(d, msg))?;
+            let body = decode_term_with_limits::<T>(d, limits, depth)?;
+
+            Ok(Term::Lambda {
+                parameter_name,
+                body: Box::new(body),
+            })
+        }
+        3 => {
+            let depth = enter_depth(depth, limits, d)?;
+            let function = Box::new(decode_term_with_limits::<T>(d, limits, depth)?);
+            let argument = Box::new(decode_term_with_limits::<T>(d, limits, depth)?);
+
+            Ok(Term::Apply { function, argument })
+        }
+        4 => Ok(Term::Constant(decode_constant_with_limits(d, limits)?)),
+        5 => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            Ok(Term::Force(Box::new(decode_term_with_limits::<T>(
+                d, limits, depth,
+            )?)))
+        }
+        6 => Ok(Term::Error),
+        7 => Ok(Term::Builtin(
+            DefaultFunction::decode(d).map_err(|msg| invalid_at(d, msg))?,
+        )),
+        x => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownTermTag(x),
+            offset: offset(d),
+        }),
+    }
+}
+
+fn decode_constant_with_limits(d: &mut Decoder, limits: &DecodeLimits) -> Result<Constant, FlatDecodeError> {
+    let tags = decode_list_bounded(d, limits, |d| {
+        d.bits8(CONST_TAG_WIDTH as usize).map_err(|_| eof_at(d))
+    })?;
+    let constant_tag_list_end = offset(d);
+    let mut tags = tags.into_iter();
+
+    let typ = decode_constant_type_tags_bounded(&mut tags, constant_tag_list_end, limits, 0)?;
+
+    if tags.next().is_some() {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::MalformedConstantTagList,
+            offset: constant_tag_list_end,
+        });
+    }
+
+    decode_constant_value_with_limits(&typ, d, limits, 0)
+}
+
+fn decode_constant_value_with_limits(
+    typ: &Type,
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    depth: usize,
+) -> Result<Constant, FlatDecodeError> {
+    match typ {
+        Type::Integer => Ok(Constant::Integer(zigzag_decode(&decode_varint_bounded(
+            d, limits,
+        )?))),
+        Type::ByteString => Ok(Constant::ByteString(decode_bytestring_bounded(d, limits)?)),
+        Type::String => {
+            let bytes = decode_bytestring_bounded(d, limits)?;
+
+            String::from_utf8(bytes)
+                .map(Constant::String)
+                .map_err(|_| FlatDecodeError {
+                    kind: FlatDecodeErrorKind::InvalidUtf8,
+                    offset: offset(d),
+                })
+        }
+        Type::Unit => Ok(Constant::Unit),
+        Type::Bool => Ok(Constant::Bool(d.bits8(1).map_err(|_| eof_at(d))? == 1)),
+        Type::List(elem) => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            let items = decode_list_bounded(d, limits, |d| {
+                decode_constant_value_with_limits(elem, d, limits, depth)
+            })?;
+
+            Ok(Constant::ProtoList(elem.as_ref().clone(), items))
+        }
+        Type::Pair(a, b) => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            let left = decode_constant_value_with_limits(a, d, limits, depth)?;
+            let right = decode_constant_value_with_limits(b, d, limits, depth)?;
+
+            Ok(Constant::ProtoPair(
+                a.as_ref().clone(),
+                b.as_ref().clone(),
+                Box::new(left),
+                Box::new(right),
+            ))
+        }
+        Type::Data => Ok(Constant::Data(decode_data_with_limits(d, limits, depth)?)),
+    }
+}
+
+fn decode_data_with_limits(
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    depth: usize,
+) -> Result<PlutusData, FlatDecodeError> {
+    let depth = enter_depth(depth, limits, d)?;
+
+    let tag = d.bits8(DATA_TAG_WIDTH as usize).map_err(|_| eof_at(d))?;
+
+    match tag {
+        0 => {
+            let tag = zigzag_decode(&decode_varint_bounded(d, limits)?);
+            let tag = usize::try_from(tag).map_err(|_| FlatDecodeError {
+                kind: FlatDecodeErrorKind::IntegerOverflow,
+                offset: offset(d),
+            })?;
+
+            Ok(PlutusData::Constr {
+                tag,
+                fields: decode_list_bounded(d, limits, |d| decode_data_with_limits(d, limits, depth))?,
+            })
+        }
+        1 => Ok(PlutusData::Map(decode_list_bounded(d, limits, |d| {
+            let k = decode_data_with_limits(d, limits, depth)?;
+            let v = decode_data_with_limits(d, limits, depth)?;
+
+            Ok((k, v))
+        })?)),
+        2 => Ok(PlutusData::List(decode_list_bounded(d, limits, |d| {
+            decode_data_with_limits(d, limits, depth)
+        })?)),
+        3 => Ok(PlutusData::I(zigzag_decode(&decode_varint_bounded(
+            d, limits,
+        )?))),
+        4 => Ok(PlutusData::B(decode_bytestring_bounded(d, limits)?)),
+        x => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownDataTag(x),
+            offset: offset(d),
+        }),
+    }
+}
+
+// The validation pass used by `Program::unflat_fast`: it walks the same
+// shape `decode_term_with_limits` does and enforces the same `limits`, but
+// never builds the `Term`/`Constant` tree it's looking at - just the tag,
+// depth, and length bookkeeping needed to reject a bad blob. This keeps
+// `unflat_fast` a single structural pass over `bytes` followed by one real,
+// allocating build pass (`decode_term_unchecked`), rather than a full build
+// pass thrown away and then repeated.
+fn validate_term<'b, T>(d: &mut Decoder, limits: &DecodeLimits, depth: usize) -> Result<(), FlatDecodeError>
+where
+    T: Binder<'b>,
+{
+    if offset(d) > limits.max_bytes {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::InputTooLarge,
+            offset: offset(d),
+        });
+    }
+
+    let tag = d.bits8(TERM_TAG_WIDTH as usize).map_err(|_| eof_at(d))?;
+
+    match tag {
+        0 => {
+            T::decode(d).map_err(|msg| invalid_at(d, msg))?;
+            Ok(())
+        }
+        1 => {
+            let depth = enter_depth(depth, limits, d)?;
+            validate_term::<T>(d, limits, depth)
+        }
+        2 => {
+            let depth = enter_depth(depth, limits, d)?;
+            T::binder_decode(d).map_err(|msg| invalid_at(d, msg))?;
+            validate_term::<T>(d, limits, depth)
+        }
+        3 => {
+            let depth = enter_depth(depth, limits, d)?;
+            validate_term::<T>(d, limits, depth)?;
+            validate_term::<T>(d, limits, depth)
+        }
+        4 => validate_constant(d, limits),
+        5 => {
+            let depth = enter_depth(depth, limits, d)?;
+            validate_term::<T>(d, limits, depth)
+        }
+        6 => Ok(()),
+        7 => {
+            DefaultFunction::decode(d).map_err(|msg| invalid_at(d, msg))?;
+            Ok(())
+        }
+        x => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownTermTag(x),
+            offset: offset(d),
+        }),
+    }
+}
+
+fn validate_constant(d: &mut Decoder, limits: &DecodeLimits) -> Result<(), FlatDecodeError> {
+    let tags = decode_list_bounded(d, limits, |d| {
+        d.bits8(CONST_TAG_WIDTH as usize).map_err(|_| eof_at(d))
+    })?;
+    let constant_tag_list_end = offset(d);
+    let mut tags = tags.into_iter();
+
+    let typ = decode_constant_type_tags_bounded(&mut tags, constant_tag_list_end, limits, 0)?;
+
+    if tags.next().is_some() {
+        return Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::MalformedConstantTagList,
+            offset: constant_tag_list_end,
+        });
+    }
+
+    validate_constant_value(&typ, d, limits, 0)
+}
+
+fn validate_constant_value(
+    typ: &Type,
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    depth: usize,
+) -> Result<(), FlatDecodeError> {
+    match typ {
+        Type::Integer => validate_varint(d, limits),
+        Type::ByteString => validate_bytestring(d, limits),
+        Type::String => {
+            // Unlike a plain bytestring this still has to land in a buffer
+            // to be checked for valid utf8, so it's the one case that keeps
+            // the allocation the bounded path already pays.
+            let bytes = decode_bytestring_bounded(d, limits)?;
+
+            std::str::from_utf8(&bytes)
+                .map(|_| ())
+                .map_err(|_| FlatDecodeError {
+                    kind: FlatDecodeErrorKind::InvalidUtf8,
+                    offset: offset(d),
+                })
+        }
+        Type::Unit => Ok(()),
+        Type::Bool => {
+            d.bits8(1).map_err(|_| eof_at(d))?;
+            Ok(())
+        }
+        Type::List(elem) => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            validate_list(d, limits, |d| validate_constant_value(elem, d, limits, depth))
+        }
+        Type::Pair(a, b) => {
+            let depth = enter_depth(depth, limits, d)?;
+
+            validate_constant_value(a, d, limits, depth)?;
+            validate_constant_value(b, d, limits, depth)
+        }
+        Type::Data => validate_data(d, limits, depth),
+    }
+}
+
+fn validate_data(d: &mut Decoder, limits: &DecodeLimits, depth: usize) -> Result<(), FlatDecodeError> {
+    let depth = enter_depth(depth, limits, d)?;
+
+    let tag = d.bits8(DATA_TAG_WIDTH as usize).map_err(|_| eof_at(d))?;
+
+    match tag {
+        0 => {
+            validate_varint(d, limits)?;
+            validate_list(d, limits, |d| validate_data(d, limits, depth))
+        }
+        1 => validate_list(d, limits, |d| {
+            validate_data(d, limits, depth)?;
+            validate_data(d, limits, depth)
+        }),
+        2 => validate_list(d, limits, |d| validate_data(d, limits, depth)),
+        3 => validate_varint(d, limits),
+        4 => validate_bytestring(d, limits),
+        x => Err(FlatDecodeError {
+            kind: FlatDecodeErrorKind::UnknownDataTag(x),
+            offset: offset(d),
+        }),
+    }
+}
+
+// The validating counterpart of `decode_list_bounded`: enforces
+// `limits.max_list_len` without collecting a `Vec`.
+fn validate_list(
+    d: &mut Decoder,
+    limits: &DecodeLimits,
+    mut elem: impl FnMut(&mut Decoder) -> Result<(), FlatDecodeError>,
+) -> Result<(), FlatDecodeError> {
+    let mut len = 0usize;
+
+    while d.bits8(1).map_err(|_| eof_at(d))? == 1 {
+        if len >= limits.max_list_len {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ListTooLong,
+                offset: offset(d),
+            });
+        }
+
+        len += 1;
+        elem(d)?;
+    }
+
+    Ok(())
+}
+
+// The validating counterpart of `decode_bytestring_bounded`: enforces
+// `limits.max_constant_bytes` while discarding each chunk instead of
+// collecting it into a `Vec<u8>`.
+fn validate_bytestring(d: &mut Decoder, limits: &DecodeLimits) -> Result<(), FlatDecodeError> {
+    let mut len = 0usize;
+
+    d.filler().map_err(|_| eof_at(d))?;
+
+    loop {
+        let chunk_len = d.bits8(8).map_err(|_| eof_at(d))? as usize;
+
+        if chunk_len == 0 {
+            break;
+        }
+
+        len += chunk_len;
+
+        if len > limits.max_constant_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ConstantTooLarge,
+                offset: offset(d),
+            });
+        }
+
+        for _ in 0..chunk_len {
+            d.bits8(8).map_err(|_| eof_at(d))?;
+        }
+    }
+
+    Ok(())
+}
+
+// The validating counterpart of `decode_varint_bounded`: enforces
+// `limits.max_constant_bytes` without assembling the `BigUint` magnitude.
+fn validate_varint(d: &mut Decoder, limits: &DecodeLimits) -> Result<(), FlatDecodeError> {
+    let mut bytes_read = 0usize;
+
+    loop {
+        if bytes_read >= limits.max_constant_bytes {
+            return Err(FlatDecodeError {
+                kind: FlatDecodeErrorKind::ConstantTooLarge,
+                offset: offset(d),
+            });
+        }
+
+        let byte = d.bits8(8).map_err(|_| eof_at(d))?;
+        bytes_read += 1;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// The infallible counterpart of `decode_list_bounded`, used only once
+// `bytes` has already been validated by `validate_term` in
+// `Program::unflat_fast`. It
+// pre-reserves capacity from what it learns as it reads instead of growing
+// a `Vec::new()` one push at a time, and special-cases the very common 0-
+// and 1-element lists so they never reallocate.
+fn decode_list_unchecked<A>(d: &mut Decoder, mut elem: impl FnMut(&mut Decoder) -> A) -> Vec<A> {
+    if d.bits8(1).expect("validated above") == 0 {
+        return Vec::new();
+    }
+
+    let first = elem(d);
+
+    if d.bits8(1).expect("validated above") == 0 {
+        let mut items = Vec::with_capacity(1);
+        items.push(first);
+        return items;
+    }
+
+    let mut items = Vec::with_capacity(4);
+    items.push(first);
+    items.push(elem(d));
+
+    while d.bits8(1).expect("validated above") == 1 {
+        items.push(elem(d));
+    }
+
+    items
+}
+
+fn decode_bytestring_unchecked(d: &mut Decoder) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    d.filler().expect("validated above");
+
+    loop {
+        let chunk_len = d.bits8(8).expect("validated above") as usize;
+
+        if chunk_len == 0 {
+            break;
+        }
+
+        bytes.reserve(chunk_len);
+
+        for _ in 0..chunk_len {
+            bytes.push(d.bits8(8).expect("validated above"));
+        }
+    }
+
+    bytes
+}
+
+fn decode_varint_unchecked(d: &mut Decoder) -> BigUint {
+    let mut magnitude = BigUint::zero();
+    let mut shift = 0u32;
+
+    loop {
+        let byte = d.bits8(8).expect("validated above");
+
+        magnitude |= BigUint::from(byte & 0x7f) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    magnitude
+}
+
+fn decode_term_unchecked<'b, T>(d: &mut Decoder) -> Term<T>
+where
+    T: Binder<'b>,
+{
+    match d.bits8(TERM_TAG_WIDTH as usize).expect("validated above") {
+        0 => Term::Var(T::decode(d).expect("validated above")),
+        1 => Term::Delay(Box::new(decode_term_unchecked::<T>(d))),
+        2 => {
+            let parameter_name = T::binder_decode(d).expect("validated above");
+            let body = Box::new(decode_term_unchecked::<T>(d));
+
+            Term::Lambda {
+                parameter_name,
+                body,
+            }
+        }
+        3 => {
+            let function = Box::new(decode_term_unchecked::<T>(d));
+            let argument = Box::new(decode_term_unchecked::<T>(d));
+
+            Term::Apply { function, argument }
+        }
+        4 => Term::Constant(decode_constant_unchecked(d)),
+        5 => Term::Force(Box::new(decode_term_unchecked::<T>(d))),
+        6 => Term::Error,
+        7 => Term::Builtin(DefaultFunction::decode(d).expect("validated above")),
+        tag => unreachable!("validated above: unknown term tag {tag}"),
+    }
+}
+
+fn decode_constant_unchecked(d: &mut Decoder) -> Constant {
+    let typ = decode_constant_type_unchecked(d);
+
+    decode_constant_value_unchecked(&typ, d)
+}
+
+fn decode_constant_type_unchecked(d: &mut Decoder) -> Type {
+    let tags = decode_list_unchecked(d, |d| {
+        d.bits8(CONST_TAG_WIDTH as usize).expect("validated above")
+    });
+
+    decode_constant_type_tags_unchecked(&mut tags.into_iter())
+}
+
+fn decode_constant_type_tags_unchecked(tags: &mut impl Iterator<Item = u8>) -> Type {
+    match tags.next().expect("validated above") {
+        0 => Type::Integer,
+        1 => Type::ByteString,
+        2 => Type::String,
+        3 => Type::Unit,
+        4 => Type::Bool,
+        5 => Type::List(Box::new(decode_constant_type_tags_unchecked(tags))),
+        6 => {
+            let a = decode_constant_type_tags_unchecked(tags);
+            let b = decode_constant_type_tags_unchecked(tags);
+
+            Type::Pair(Box::new(a), Box::new(b))
+        }
+        7 => Type::Data,
+        tag => unreachable!("validated above: unknown constant type tag {tag}"),
+    }
+}
+
+fn decode_constant_value_unchecked(typ: &Type, d: &mut Decoder) -> Constant {
+    match typ {
+        Type::Integer => Constant::Integer(zigzag_decode(&decode_varint_unchecked(d))),
+        Type::ByteString => Constant::ByteString(decode_bytestring_unchecked(d)),
+        Type::String => {
+            let bytes = decode_bytestring_unchecked(d);
+
+            Constant::String(String::from_utf8(bytes).expect("validated above"))
+        }
+        Type::Unit => Constant::Unit,
+        Type::Bool => Constant::Bool(d.bits8(1).expect("validated above") == 1),
+        Type::List(elem) => {
+            let items = decode_list_unchecked(d, |d| decode_constant_value_unchecked(elem, d));
+
+            Constant::ProtoList(elem.as_ref().clone(), items)
+        }
+        Type::Pair(a, b) => {
+            let left = decode_constant_value_unchecked(a, d);
+            let right = decode_constant_value_unchecked(b, d);
+
+            Constant::ProtoPair(a.as_ref().clone(), b.as_ref().clone(), Box::new(left), Box::new(right))
+        }
+        Type::Data => Constant::Data(decode_data_unchecked(d)),
+    }
+}
+
+fn decode_data_unchecked(d: &mut Decoder) -> PlutusData {
+    match d.bits8(DATA_TAG_WIDTH as usize).expect("validated above") {
+        0 => {
+            let tag = zigzag_decode(&decode_varint_unchecked(d));
+            let tag = usize::try_from(tag).expect("validated above");
+
+            PlutusData::Constr {
+                tag,
+                fields: decode_list_unchecked(d, decode_data_unchecked),
+            }
+        }
+        1 => PlutusData::Map(decode_list_unchecked(d, |d| {
+            let k = decode_data_unchecked(d);
+            let v = decode_data_unchecked(d);
+
+            (k, v)
+        })),
+        2 => PlutusData::List(decode_list_unchecked(d, decode_data_unchecked)),
+        3 => PlutusData::I(zigzag_decode(&decode_varint_unchecked(d))),
+        4 => PlutusData::B(decode_bytestring_unchecked(d)),
+        tag => unreachable!("validated above: unknown data tag {tag}"),
+    }
 }
 
 #[cfg(test)]
 mod test {
     use flat::Flat;
+    use num_bigint::BigInt;
 
-    use crate::ast::Name;
+    use crate::{
+        ast::{Name, Type},
+        data::PlutusData,
+    };
 
-    use super::{Constant, Program, Term};
+    use super::{Constant, DecodeLimits, Program, Term};
 
     #[test]
-    fn flat_encode_integer() {
+    fn disassemble_assemble_roundtrip_with_list_pair_and_data_constants() {
         let program = Program::<Name> {
-            version: (11, 22, 33),
-            term: Term::Constant(Constant::Integer(11)),
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::ProtoPair(
+                Type::List(Box::new(Type::Integer)),
+                Type::Data,
+                Box::new(Constant::ProtoList(
+                    Type::Integer,
+                    vec![
+                        Constant::Integer(BigInt::from(1)),
+                        Constant::Integer(BigInt::from(2)),
+                    ],
+                )),
+                Box::new(Constant::Data(PlutusData::Constr {
+                    tag: 0,
+                    fields: vec![PlutusData::I(BigInt::from(42)), PlutusData::B(vec![1, 2, 3])],
+                })),
+            )),
         };
 
         let bytes = program.to_flat().unwrap();
 
-        assert_eq!(
-            bytes,
-            vec![0b00001011, 0b00010110, 0b00100001, 0b01001000, 0b00000101, 0b10000001]
-        )
+        let source = Program::<Name>::disassemble(&bytes).unwrap();
+        let reassembled = Program::<Name>::assemble(&source).unwrap();
+
+        assert_eq!(reassembled, bytes);
     }
 
     #[test]
-    fn flat_decode_integer() {
-        let flat_encoded = vec![
-            0b00001011, 0b00010110, 0b00100001, 0b01001000, 0b00000101, 0b10000001,
-        ];
+    fn disassemble_assemble_roundtrip_with_quoted_string_constant() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::String(r#"say "hi" \ bye"#.to_string())),
+        };
+
+        let bytes = program.to_flat().unwrap();
 
-        let expected_program = Program {
+        let source = Program::<Name>::disassemble(&bytes).unwrap();
+        let reassembled = Program::<Name>::assemble(&source).unwrap();
+
+        assert_eq!(reassembled, bytes);
+    }
+
+    #[test]
+    fn flat_roundtrip_integer() {
+        let program = Program::<Name> {
             version: (11, 22, 33),
-            term: Term::Constant(Constant::Integer(11)),
+            term: Term::Constant(Constant::Integer(BigInt::from(11))),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> = Program::unflat(&bytes).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn flat_roundtrip_large_negative_integer() {
+        // bigger than a machine isize/i64 on some targets, and negative, to
+        // exercise both the zig-zag sign mapping and the varint's multi-byte path
+        let huge = BigInt::from(10).pow(40) * BigInt::from(-1);
+
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Integer(huge)),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> = Program::unflat(&bytes).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn flat_roundtrip_list_of_integers() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::ProtoList(
+                Type::Integer,
+                vec![
+                    Constant::Integer(BigInt::from(1)),
+                    Constant::Integer(BigInt::from(2)),
+                    Constant::Integer(BigInt::from(3)),
+                ],
+            )),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> = Program::unflat(&bytes).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn flat_roundtrip_pair() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::ProtoPair(
+                Type::Integer,
+                Type::Bool,
+                Box::new(Constant::Integer(BigInt::from(14))),
+                Box::new(Constant::Bool(true)),
+            )),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> = Program::unflat(&bytes).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn flat_roundtrip_data() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Data(PlutusData::Constr {
+                tag: 0,
+                fields: vec![PlutusData::I(BigInt::from(42)), PlutusData::B(vec![1, 2, 3])],
+            })),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> = Program::unflat(&bytes).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn flat_decode_reports_unexpected_eof() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Integer(BigInt::from(11))),
+        };
+
+        let mut bytes = program.to_flat().unwrap();
+        bytes.truncate(bytes.len() - 1);
+
+        let err = Program::<Name>::unflat(&bytes).unwrap_err();
+
+        assert!(err.contains("unexpected end of input"), "{err}");
+    }
+
+    #[test]
+    fn unflat_with_limits_accepts_programs_within_bounds() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Integer(BigInt::from(11))),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> =
+            Program::unflat_with_limits(&bytes, DecodeLimits::default()).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn unflat_with_limits_rejects_deeply_nested_terms() {
+        let mut term = Term::Error;
+
+        for _ in 0..10 {
+            term = Term::Delay(Box::new(term));
+        }
+
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term,
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 5,
+            ..DecodeLimits::default()
+        };
+
+        let err = Program::<Name>::unflat_with_limits(&bytes, limits).unwrap_err();
+
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::NestingTooDeep);
+    }
+
+    #[test]
+    fn unflat_with_limits_rejects_deeply_nested_data() {
+        let mut data = PlutusData::I(BigInt::from(0));
+
+        for _ in 0..10 {
+            data = PlutusData::Constr {
+                tag: 0,
+                fields: vec![data],
+            };
+        }
+
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Data(data)),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 5,
+            ..DecodeLimits::default()
+        };
+
+        let err = Program::<Name>::unflat_with_limits(&bytes, limits).unwrap_err();
+
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::NestingTooDeep);
+    }
+
+    fn deeply_nested_list_type_constant() -> Constant {
+        let mut typ = Type::Integer;
+
+        for _ in 0..10 {
+            typ = Type::List(Box::new(typ));
+        }
+
+        Constant::ProtoList(typ, vec![])
+    }
+
+    #[test]
+    fn unflat_with_limits_rejects_deeply_nested_constant_type() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(deeply_nested_list_type_constant()),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 5,
+            ..DecodeLimits::default()
+        };
+
+        let err = Program::<Name>::unflat_with_limits(&bytes, limits).unwrap_err();
+
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::NestingTooDeep);
+    }
+
+    #[test]
+    fn unflat_fast_rejects_deeply_nested_constant_type() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(deeply_nested_list_type_constant()),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 5,
+            ..DecodeLimits::default()
+        };
+
+        let err = Program::<Name>::unflat_fast(&bytes, limits).unwrap_err();
+
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::NestingTooDeep);
+    }
+
+    #[test]
+    fn unflat_fast_matches_the_fallible_decoder() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::ProtoList(
+                Type::Integer,
+                vec![
+                    Constant::Integer(BigInt::from(1)),
+                    Constant::Integer(BigInt::from(2)),
+                    Constant::Integer(BigInt::from(3)),
+                ],
+            )),
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let actual_program: Program<Name> =
+            Program::unflat_fast(&bytes, DecodeLimits::default()).unwrap();
+
+        assert_eq!(actual_program, program)
+    }
+
+    #[test]
+    fn unflat_fast_rejects_truncated_bytes() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Integer(BigInt::from(11))),
+        };
+
+        let mut bytes = program.to_flat().unwrap();
+        bytes.truncate(bytes.len() - 1);
+
+        let err = Program::<Name>::unflat_fast(&bytes, DecodeLimits::default()).unwrap_err();
+
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn unflat_fast_rejects_deeply_nested_terms() {
+        let mut term = Term::Error;
+
+        for _ in 0..10 {
+            term = Term::Delay(Box::new(term));
+        }
+
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term,
+        };
+
+        let bytes = program.to_flat().unwrap();
+
+        let limits = DecodeLimits {
+            max_depth: 5,
+            ..DecodeLimits::default()
         };
 
-        let actual_program: Program<Name> = Program::unflat(&flat_encoded).unwrap();
+        let err = Program::<Name>::unflat_fast(&bytes, limits).unwrap_err();
 
-        assert_eq!(actual_program, expected_program)
+        assert_eq!(err.kind, super::FlatDecodeErrorKind::NestingTooDeep);
     }
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// A structured error produced while decoding a flat-encoded [`Program`](crate::ast::Program),
+/// carrying the byte offset into the input where the problem was found so
+/// callers can render a proper diagnostic with a span, rather than the bare
+/// `String` the underlying `flat` codec otherwise returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatDecodeError {
+    pub kind: FlatDecodeErrorKind,
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlatDecodeErrorKind {
+    UnexpectedEof,
+    UnknownTermTag(u8),
+    UnknownConstantTag(u8),
+    UnknownDataTag(u8),
+    MalformedConstantTagList,
+    IntegerOverflow,
+    InvalidUtf8,
+    /// The input exceeded [`DecodeLimits::max_bytes`](crate::flat::DecodeLimits::max_bytes).
+    InputTooLarge,
+    /// A `Delay`/`Force`/`Lambda`/`Apply` spine, or a constant's type tags,
+    /// list/pair values, or `Data` value, nested deeper than
+    /// [`DecodeLimits::max_depth`](crate::flat::DecodeLimits::max_depth).
+    NestingTooDeep,
+    /// A bytestring, string, or integer constant was larger than
+    /// [`DecodeLimits::max_constant_bytes`](crate::flat::DecodeLimits::max_constant_bytes).
+    ConstantTooLarge,
+    /// A flat list had more elements than
+    /// [`DecodeLimits::max_list_len`](crate::flat::DecodeLimits::max_list_len).
+    ListTooLong,
+    /// A sub-decoder outside this crate's control (a [`Binder`](crate::flat::Binder)
+    /// or [`DefaultFunction`](crate::builtins::DefaultFunction)) failed; carries
+    /// its original message instead of guessing at the failure.
+    Invalid(String),
+}
+
+impl fmt::Display for FlatDecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlatDecodeErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            FlatDecodeErrorKind::UnknownTermTag(tag) => {
+                write!(f, "unknown term constructor tag: {tag}")
+            }
+            FlatDecodeErrorKind::UnknownConstantTag(tag) => {
+                write!(f, "unknown constant type tag: {tag}")
+            }
+            FlatDecodeErrorKind::UnknownDataTag(tag) => {
+                write!(f, "unknown data constructor tag: {tag}")
+            }
+            FlatDecodeErrorKind::MalformedConstantTagList => {
+                write!(f, "malformed constant type tag list")
+            }
+            FlatDecodeErrorKind::IntegerOverflow => {
+                write!(f, "integer does not fit in the expected range")
+            }
+            FlatDecodeErrorKind::InvalidUtf8 => write!(f, "string constant is not valid utf8"),
+            FlatDecodeErrorKind::InputTooLarge => write!(f, "input exceeds the maximum decodable size"),
+            FlatDecodeErrorKind::NestingTooDeep => write!(f, "term is nested too deeply"),
+            FlatDecodeErrorKind::ConstantTooLarge => write!(f, "constant exceeds the maximum decodable size"),
+            FlatDecodeErrorKind::ListTooLong => write!(f, "list exceeds the maximum decodable length"),
+            FlatDecodeErrorKind::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl fmt::Display for FlatDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.kind, self.offset)
+    }
+}
+
+impl std::error::Error for FlatDecodeError {}
+
+// The `flat` crate's `Decode`/`Encode` traits fix their error type to
+// `String`, so this lets call sites build a structured `FlatDecodeError`
+// and bubble it out through `?` at the trait boundary without losing the
+// offset and kind while still inside this crate's own decode functions.
+impl From<FlatDecodeError> for String {
+    fn from(err: FlatDecodeError) -> Self {
+        err.to_string()
+    }
+}
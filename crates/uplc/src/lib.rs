@@ -0,0 +1,7 @@
+pub mod ast;
+pub mod builtins;
+pub mod data;
+pub mod flat;
+pub mod flat_error;
+pub mod parser;
+pub mod pretty;
@@ -0,0 +1,708 @@
+use std::{collections::HashMap, fmt, ops::Range, str::CharIndices};
+
+use num_bigint::BigInt;
+
+use crate::{
+    ast::{Constant, Name, Program, Term, Type, Unique},
+    builtins::DefaultFunction,
+    data::PlutusData,
+};
+
+/// A single error produced while reading the textual UPLC surface syntax,
+/// carrying the byte offset into the source where it occurred so callers
+/// can render a proper diagnostic with a span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserError {
+    pub kind: ErrorKind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnexpectedEof,
+    UnexpectedToken { found: String, expected: String },
+    InvalidVersion(String),
+    InvalidInteger(String),
+    InvalidByteString(String),
+    UnknownConstantType(String),
+    UnknownBuiltin(String),
+    UnboundVariable(String),
+    InvalidEscape(char),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ErrorKind::UnexpectedToken { found, expected } => {
+                write!(f, "expected {expected}, found '{found}'")
+            }
+            ErrorKind::InvalidVersion(s) => write!(f, "invalid program version: '{s}'"),
+            ErrorKind::InvalidInteger(s) => write!(f, "invalid integer literal: '{s}'"),
+            ErrorKind::InvalidByteString(s) => write!(f, "invalid bytestring literal: '{s}'"),
+            ErrorKind::UnknownConstantType(s) => write!(f, "unknown constant type: '{s}'"),
+            ErrorKind::UnknownBuiltin(s) => write!(f, "unknown builtin: '{s}'"),
+            ErrorKind::UnboundVariable(s) => write!(f, "unbound variable: '{s}'"),
+            ErrorKind::InvalidEscape(c) => write!(f, "invalid escape sequence: '\\{c}'"),
+        }
+    }
+}
+
+impl fmt::Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for ParserError {}
+
+/// Parse the textual UPLC surface syntax (the same syntax produced by
+/// [`Display`](std::fmt::Display) on [`Program`]) into a [`Program<Name>`].
+pub fn parse(source: &str) -> Result<Program<Name>, ParserError> {
+    let mut parser = Parser::new(source);
+
+    let program = parser.program()?;
+
+    parser.skip_whitespace();
+
+    if let Some((pos, c)) = parser.peek() {
+        return Err(parser.error_at(
+            pos,
+            ErrorKind::UnexpectedToken {
+                found: c.to_string(),
+                expected: "end of input".to_string(),
+            },
+        ));
+    }
+
+    Ok(program)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    chars: std::iter::Peekable<CharIndices<'a>>,
+    next_unique: isize,
+    scopes: Vec<HashMap<&'a str, Unique>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            source,
+            chars: source.char_indices().peekable(),
+            next_unique: 0,
+            scopes: vec![HashMap::new()],
+        }
+    }
+
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<(usize, char)> {
+        self.chars.next()
+    }
+
+    fn error_at(&self, pos: usize, kind: ErrorKind) -> ParserError {
+        ParserError {
+            kind,
+            span: pos..pos + 1,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some((_, c)) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect_char(&mut self, expected: char) -> Result<(), ParserError> {
+        self.skip_whitespace();
+
+        match self.bump() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((pos, c)) => Err(self.error_at(
+                pos,
+                ErrorKind::UnexpectedToken {
+                    found: c.to_string(),
+                    expected: expected.to_string(),
+                },
+            )),
+            None => Err(ParserError {
+                kind: ErrorKind::UnexpectedEof,
+                span: self.source.len()..self.source.len(),
+            }),
+        }
+    }
+
+    // A bare word made up of anything that isn't whitespace or a delimiter.
+    fn word(&mut self) -> Result<(usize, &'a str), ParserError> {
+        self.skip_whitespace();
+
+        let start = match self.peek() {
+            Some((pos, _)) => pos,
+            None => {
+                return Err(ParserError {
+                    kind: ErrorKind::UnexpectedEof,
+                    span: self.source.len()..self.source.len(),
+                })
+            }
+        };
+
+        let mut end = start;
+
+        while let Some((pos, c)) = self.peek() {
+            if c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | ',') {
+                break;
+            }
+            end = pos + c.len_utf8();
+            self.bump();
+        }
+
+        Ok((start, &self.source[start..end]))
+    }
+
+    fn keyword(&mut self, expected: &'static str) -> Result<(), ParserError> {
+        let (pos, found) = self.word()?;
+
+        if found == expected {
+            Ok(())
+        } else {
+            Err(self.error_at(
+                pos,
+                ErrorKind::UnexpectedToken {
+                    found: found.to_string(),
+                    expected: expected.to_string(),
+                },
+            ))
+        }
+    }
+
+    fn program(&mut self) -> Result<Program<Name>, ParserError> {
+        self.expect_char('(')?;
+        self.keyword("program")?;
+
+        let (pos, raw_version) = self.word()?;
+        let version = parse_version(raw_version).ok_or_else(|| {
+            self.error_at(pos, ErrorKind::InvalidVersion(raw_version.to_string()))
+        })?;
+
+        let term = self.term()?;
+
+        self.expect_char(')')?;
+
+        Ok(Program { version, term })
+    }
+
+    fn term(&mut self) -> Result<Term<Name>, ParserError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some((_, '(')) => self.parenthesized_term(),
+            Some((_, '[')) => self.application(),
+            Some(_) => {
+                let (pos, name) = self.word()?;
+                self.resolve(pos, name).map(Term::Var)
+            }
+            None => Err(ParserError {
+                kind: ErrorKind::UnexpectedEof,
+                span: self.source.len()..self.source.len(),
+            }),
+        }
+    }
+
+    fn parenthesized_term(&mut self) -> Result<Term<Name>, ParserError> {
+        self.expect_char('(')?;
+
+        let (pos, keyword) = self.word()?;
+
+        let term = match keyword {
+            "lam" => {
+                let (_, param) = self.word()?;
+
+                self.push_binder(param);
+
+                let body = self.term()?;
+                let parameter_name = self.pop_binder(param);
+
+                Term::Lambda {
+                    parameter_name,
+                    body: Box::new(body),
+                }
+            }
+            "delay" => Term::Delay(Box::new(self.term()?)),
+            "force" => Term::Force(Box::new(self.term()?)),
+            "error" => Term::Error,
+            "builtin" => {
+                let (pos, name) = self.word()?;
+                Term::Builtin(parse_builtin(name).ok_or_else(|| {
+                    self.error_at(pos, ErrorKind::UnknownBuiltin(name.to_string()))
+                })?)
+            }
+            "con" => Term::Constant(self.constant()?),
+            _ => {
+                return Err(self.error_at(
+                    pos,
+                    ErrorKind::UnexpectedToken {
+                        found: keyword.to_string(),
+                        expected: "lam, delay, force, error, builtin or con".to_string(),
+                    },
+                ))
+            }
+        };
+
+        self.expect_char(')')?;
+
+        Ok(term)
+    }
+
+    // `[ f x y z ]` desugars to left-associative applications: `(((f x) y) z)`.
+    fn application(&mut self) -> Result<Term<Name>, ParserError> {
+        self.expect_char('[')?;
+
+        let mut function = self.term()?;
+
+        loop {
+            self.skip_whitespace();
+
+            if let Some((_, ']')) = self.peek() {
+                break;
+            }
+
+            let argument = self.term()?;
+
+            function = Term::Apply {
+                function: Box::new(function),
+                argument: Box::new(argument),
+            };
+        }
+
+        self.expect_char(']')?;
+
+        Ok(function)
+    }
+
+    // `(con <type> <value>)` is parsed in two steps, mirroring the
+    // `Display for Constant`/`Type` grammar in `pretty.rs`: first the type
+    // (a bare word, or `(list T)`/`(pair A B)`), then a value shaped to
+    // match it.
+    fn constant(&mut self) -> Result<Constant, ParserError> {
+        let typ = self.typ()?;
+
+        self.constant_value(&typ)
+    }
+
+    fn typ(&mut self) -> Result<Type, ParserError> {
+        self.skip_whitespace();
+
+        if let Some((_, '(')) = self.peek() {
+            self.expect_char('(')?;
+
+            let (pos, keyword) = self.word()?;
+
+            let typ = match keyword {
+                "list" => Type::List(Box::new(self.typ()?)),
+                "pair" => {
+                    let a = self.typ()?;
+                    let b = self.typ()?;
+
+                    Type::Pair(Box::new(a), Box::new(b))
+                }
+                _ => {
+                    return Err(self.error_at(
+                        pos,
+                        ErrorKind::UnexpectedToken {
+                            found: keyword.to_string(),
+                            expected: "list or pair".to_string(),
+                        },
+                    ))
+                }
+            };
+
+            self.expect_char(')')?;
+
+            Ok(typ)
+        } else {
+            let (pos, name) = self.word()?;
+
+            match name {
+                "integer" => Ok(Type::Integer),
+                "bytestring" => Ok(Type::ByteString),
+                "string" => Ok(Type::String),
+                "unit" => Ok(Type::Unit),
+                "bool" => Ok(Type::Bool),
+                "data" => Ok(Type::Data),
+                _ => Err(self.error_at(pos, ErrorKind::UnknownConstantType(name.to_string()))),
+            }
+        }
+    }
+
+    // The value half of a constant, shaped by `typ` - e.g. for `Type::List`
+    // this is the bracketed `[v1, v2, ...]` that follows `(list T)` in
+    // `(con (list T) [v1, v2, ...])`, and recurses the same way for the
+    // elements of a list or pair.
+    fn constant_value(&mut self, typ: &Type) -> Result<Constant, ParserError> {
+        match typ {
+            Type::Integer => {
+                let (pos, raw) = self.word()?;
+                let value: BigInt = raw
+                    .parse()
+                    .map_err(|_| self.error_at(pos, ErrorKind::InvalidInteger(raw.to_string())))?;
+                Ok(Constant::Integer(value))
+            }
+            Type::ByteString => Ok(Constant::ByteString(self.hex_bytes()?)),
+            Type::String => Ok(Constant::String(self.quoted_string()?)),
+            Type::Unit => {
+                self.expect_char('(')?;
+                self.expect_char(')')?;
+                Ok(Constant::Unit)
+            }
+            Type::Bool => Ok(Constant::Bool(self.boolean()?)),
+            Type::List(elem) => {
+                let items = self.bracketed_values(|p| p.constant_value(elem))?;
+
+                Ok(Constant::ProtoList(elem.as_ref().clone(), items))
+            }
+            Type::Pair(a, b) => {
+                self.expect_char('(')?;
+                let left = self.constant_value(a)?;
+                self.skip_whitespace();
+                self.expect_char(',')?;
+                let right = self.constant_value(b)?;
+                self.expect_char(')')?;
+
+                Ok(Constant::ProtoPair(
+                    a.as_ref().clone(),
+                    b.as_ref().clone(),
+                    Box::new(left),
+                    Box::new(right),
+                ))
+            }
+            Type::Data => Ok(Constant::Data(self.plutus_data()?)),
+        }
+    }
+
+    fn hex_bytes(&mut self) -> Result<Vec<u8>, ParserError> {
+        let (pos, raw) = self.word()?;
+        let raw = raw
+            .strip_prefix('#')
+            .ok_or_else(|| self.error_at(pos, ErrorKind::InvalidByteString(raw.to_string())))?;
+
+        hex::decode(raw).map_err(|_| self.error_at(pos, ErrorKind::InvalidByteString(raw.to_string())))
+    }
+
+    fn boolean(&mut self) -> Result<bool, ParserError> {
+        let (pos, raw) = self.word()?;
+
+        match raw {
+            "True" => Ok(true),
+            "False" => Ok(false),
+            _ => Err(self.error_at(
+                pos,
+                ErrorKind::UnexpectedToken {
+                    found: raw.to_string(),
+                    expected: "True or False".to_string(),
+                },
+            )),
+        }
+    }
+
+    // Parses a `[item, item, ...]` list, distinct from the `[ f x y ]`
+    // application syntax `term()`/`application()` handle: commas separate
+    // elements here instead of whitespace separating the spine of applied
+    // arguments.
+    fn bracketed_values<A>(
+        &mut self,
+        mut one: impl FnMut(&mut Parser<'a>) -> Result<A, ParserError>,
+    ) -> Result<Vec<A>, ParserError> {
+        self.expect_char('[')?;
+
+        let mut items = Vec::new();
+
+        self.skip_whitespace();
+
+        if let Some((_, ']')) = self.peek() {
+            self.bump();
+            return Ok(items);
+        }
+
+        loop {
+            items.push(one(self)?);
+
+            self.skip_whitespace();
+
+            match self.bump() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => break,
+                Some((pos, c)) => {
+                    return Err(self.error_at(
+                        pos,
+                        ErrorKind::UnexpectedToken {
+                            found: c.to_string(),
+                            expected: "',' or ']'".to_string(),
+                        },
+                    ))
+                }
+                None => {
+                    return Err(ParserError {
+                        kind: ErrorKind::UnexpectedEof,
+                        span: self.source.len()..self.source.len(),
+                    })
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    // Parses the `PlutusData` literal grammar `Display for PlutusData`
+    // emits: `Constr <tag> [...]`, `Map [(k, v), ...]`, `List [...]`,
+    // `I <integer>`, `B #<hex>`.
+    fn plutus_data(&mut self) -> Result<PlutusData, ParserError> {
+        let (pos, keyword) = self.word()?;
+
+        match keyword {
+            "Constr" => {
+                let (pos, raw) = self.word()?;
+                let tag: usize = raw
+                    .parse()
+                    .map_err(|_| self.error_at(pos, ErrorKind::InvalidInteger(raw.to_string())))?;
+                let fields = self.bracketed_values(|p| p.plutus_data())?;
+
+                Ok(PlutusData::Constr { tag, fields })
+            }
+            "Map" => {
+                let pairs = self.bracketed_values(|p| {
+                    p.expect_char('(')?;
+                    let k = p.plutus_data()?;
+                    p.skip_whitespace();
+                    p.expect_char(',')?;
+                    let v = p.plutus_data()?;
+                    p.expect_char(')')?;
+
+                    Ok((k, v))
+                })?;
+
+                Ok(PlutusData::Map(pairs))
+            }
+            "List" => Ok(PlutusData::List(self.bracketed_values(|p| p.plutus_data())?)),
+            "I" => {
+                let (pos, raw) = self.word()?;
+                let value: BigInt = raw
+                    .parse()
+                    .map_err(|_| self.error_at(pos, ErrorKind::InvalidInteger(raw.to_string())))?;
+
+                Ok(PlutusData::I(value))
+            }
+            "B" => Ok(PlutusData::B(self.hex_bytes()?)),
+            _ => Err(self.error_at(
+                pos,
+                ErrorKind::UnexpectedToken {
+                    found: keyword.to_string(),
+                    expected: "Constr, Map, List, I or B".to_string(),
+                },
+            )),
+        }
+    }
+
+    fn quoted_string(&mut self) -> Result<String, ParserError> {
+        self.expect_char('"')?;
+
+        let mut out = String::new();
+
+        loop {
+            match self.bump() {
+                Some((_, '"')) => break,
+                Some((_, '\\')) => match self.bump() {
+                    Some((_, '"')) => out.push('"'),
+                    Some((_, '\\')) => out.push('\\'),
+                    Some((pos, c)) => return Err(self.error_at(pos, ErrorKind::InvalidEscape(c))),
+                    None => {
+                        return Err(ParserError {
+                            kind: ErrorKind::UnexpectedEof,
+                            span: self.source.len()..self.source.len(),
+                        })
+                    }
+                },
+                Some((_, c)) => out.push(c),
+                None => {
+                    return Err(ParserError {
+                        kind: ErrorKind::UnexpectedEof,
+                        span: self.source.len()..self.source.len(),
+                    })
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn push_binder(&mut self, name: &'a str) {
+        let unique = Unique::from(self.next_unique);
+        self.next_unique += 1;
+        self.scopes.push(HashMap::from([(name, unique)]));
+    }
+
+    fn pop_binder(&mut self, name: &'a str) -> Name {
+        let scope = self.scopes.pop().expect("push_binder/pop_binder mismatch");
+        let unique = scope[name];
+
+        Name {
+            text: name.to_string(),
+            unique,
+        }
+    }
+
+    fn resolve(&self, pos: usize, name: &'a str) -> Result<Name, ParserError> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(unique) = scope.get(name) {
+                return Ok(Name {
+                    text: name.to_string(),
+                    unique: *unique,
+                });
+            }
+        }
+
+        Err(self.error_at(pos, ErrorKind::UnboundVariable(name.to_string())))
+    }
+}
+
+fn parse_version(raw: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = raw.split('.');
+
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((major, minor, patch))
+}
+
+fn parse_builtin(name: &str) -> Option<DefaultFunction> {
+    (0..=u8::MAX)
+        .filter_map(|tag| DefaultFunction::try_from(tag).ok())
+        .find(|builtin| crate::pretty::builtin_name(builtin) == name)
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigInt;
+
+    use crate::{
+        ast::{Constant, Program, Term, Type},
+        data::PlutusData,
+    };
+
+    use super::parse;
+
+    #[test]
+    fn parses_simple_constant() {
+        let program = parse("(program 1.0.0 (con integer 11))").unwrap();
+
+        assert_eq!(
+            program,
+            Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::Integer(BigInt::from(11))),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_list_constant() {
+        let program = parse("(program 1.0.0 (con (list integer) [1, 2, 3]))").unwrap();
+
+        assert_eq!(
+            program,
+            Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::ProtoList(
+                    Type::Integer,
+                    vec![
+                        Constant::Integer(BigInt::from(1)),
+                        Constant::Integer(BigInt::from(2)),
+                        Constant::Integer(BigInt::from(3)),
+                    ],
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_empty_list_constant() {
+        let program = parse("(program 1.0.0 (con (list integer) []))").unwrap();
+
+        assert_eq!(
+            program,
+            Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::ProtoList(Type::Integer, vec![])),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_pair_constant() {
+        let program = parse("(program 1.0.0 (con (pair integer bool) (14, True)))").unwrap();
+
+        assert_eq!(
+            program,
+            Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::ProtoPair(
+                    Type::Integer,
+                    Type::Bool,
+                    Box::new(Constant::Integer(BigInt::from(14))),
+                    Box::new(Constant::Bool(true)),
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_data_constant() {
+        let program = parse("(program 1.0.0 (con data Constr 0 [I 42, B #010203]))").unwrap();
+
+        assert_eq!(
+            program,
+            Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::Data(PlutusData::Constr {
+                    tag: 0,
+                    fields: vec![PlutusData::I(BigInt::from(42)), PlutusData::B(vec![1, 2, 3])],
+                })),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_lambda_application() {
+        let program = parse(
+            "(program 1.0.0 (lam i_0 [ (builtin addInteger) (con integer 1) i_0 ]))",
+        )
+        .unwrap();
+
+        match program.term {
+            Term::Lambda {
+                parameter_name,
+                body,
+            } => {
+                assert_eq!(parameter_name.text, "i_0");
+                assert!(matches!(*body, Term::Apply { .. }));
+            }
+            _ => panic!("expected a lambda"),
+        }
+    }
+
+    #[test]
+    fn rejects_unbound_variable() {
+        assert!(parse("(program 1.0.0 x)").is_err());
+    }
+}
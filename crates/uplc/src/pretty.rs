@@ -0,0 +1,258 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    ast::{Constant, Program, Term, Type},
+    builtins::DefaultFunction,
+    data::PlutusData,
+};
+
+impl<T> Display for Program<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor, patch) = self.version;
+
+        write!(f, "(program {major}.{minor}.{patch} {})", self.term)
+    }
+}
+
+impl<T> Display for Term<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(name) => write!(f, "{name}"),
+            Term::Delay(term) => write!(f, "(delay {term})"),
+            Term::Force(term) => write!(f, "(force {term})"),
+            Term::Error => write!(f, "(error)"),
+            Term::Builtin(builtin) => write!(f, "(builtin {})", builtin_name(builtin)),
+            Term::Constant(constant) => write!(f, "{constant}"),
+            Term::Lambda {
+                parameter_name,
+                body,
+            } => write!(f, "(lam {parameter_name} {body})"),
+            Term::Apply { .. } => {
+                // Unroll the spine of nested applications so `[ f x y ]` prints
+                // flat instead of as `[ [ [ f x ] y ] ]`-style nesting.
+                let mut arguments = vec![];
+                let mut function = self;
+
+                while let Term::Apply {
+                    function: inner,
+                    argument,
+                } = function
+                {
+                    arguments.push(argument.as_ref());
+                    function = inner;
+                }
+
+                write!(f, "[ {function}")?;
+
+                for argument in arguments.into_iter().rev() {
+                    write!(f, " {argument}")?;
+                }
+
+                write!(f, " ]")
+            }
+        }
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constant::Integer(i) => write!(f, "(con integer {i})"),
+            Constant::ByteString(bytes) => write!(f, "(con bytestring #{})", hex::encode(bytes)),
+            Constant::String(s) => write!(f, "(con string \"{}\")", escape_string(s)),
+            Constant::Char(c) => write!(f, "(con char '{c}')"),
+            Constant::Unit => write!(f, "(con unit ())"),
+            Constant::Bool(b) => write!(f, "(con bool {})", if *b { "True" } else { "False" }),
+            Constant::ProtoList(typ, items) => {
+                write!(f, "(con (list {typ}) [")?;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ConstantValue(item))?;
+                }
+
+                write!(f, "])")
+            }
+            Constant::ProtoPair(a, b, left, right) => write!(
+                f,
+                "(con (pair {a} {b}) ({}, {}))",
+                ConstantValue(left),
+                ConstantValue(right)
+            ),
+            Constant::Data(data) => write!(f, "(con data {data})"),
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Integer => write!(f, "integer"),
+            Type::ByteString => write!(f, "bytestring"),
+            Type::String => write!(f, "string"),
+            Type::Unit => write!(f, "unit"),
+            Type::Bool => write!(f, "bool"),
+            Type::List(elem) => write!(f, "(list {elem})"),
+            Type::Pair(a, b) => write!(f, "(pair {a} {b})"),
+            Type::Data => write!(f, "data"),
+        }
+    }
+}
+
+/// Escape `"` and `\` so the result round-trips back through
+/// [`Parser::quoted_string`](crate::parser::Parser), which unescapes `\"`
+/// and `\\`.
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+
+        out.push(c);
+    }
+
+    out
+}
+
+/// The bare value of a [`Constant`], without the surrounding `(con <type>
+/// ...)` wrapper, used to print the elements of a list or pair.
+struct ConstantValue<'a>(&'a Constant);
+
+impl Display for ConstantValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Constant::Integer(i) => write!(f, "{i}"),
+            Constant::ByteString(bytes) => write!(f, "#{}", hex::encode(bytes)),
+            Constant::String(s) => write!(f, "\"{}\"", escape_string(s)),
+            Constant::Char(c) => write!(f, "'{c}'"),
+            Constant::Unit => write!(f, "()"),
+            Constant::Bool(b) => write!(f, "{}", if *b { "True" } else { "False" }),
+            Constant::ProtoList(_, items) => {
+                write!(f, "[")?;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ConstantValue(item))?;
+                }
+
+                write!(f, "]")
+            }
+            Constant::ProtoPair(_, _, left, right) => {
+                write!(f, "({}, {})", ConstantValue(left), ConstantValue(right))
+            }
+            Constant::Data(data) => write!(f, "{data}"),
+        }
+    }
+}
+
+impl Display for PlutusData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlutusData::Constr { tag, fields } => {
+                write!(f, "Constr {tag} [")?;
+
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{field}")?;
+                }
+
+                write!(f, "]")
+            }
+            PlutusData::Map(pairs) => {
+                write!(f, "Map [")?;
+
+                for (i, (k, v)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "({k}, {v})")?;
+                }
+
+                write!(f, "]")
+            }
+            PlutusData::List(items) => {
+                write!(f, "List [")?;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+
+                write!(f, "]")
+            }
+            PlutusData::I(i) => write!(f, "I {i}"),
+            PlutusData::B(bytes) => write!(f, "B #{}", hex::encode(bytes)),
+        }
+    }
+}
+
+/// The lowerCamelCase name a builtin is referred to by in the UPLC surface
+/// syntax, e.g. `DefaultFunction::AddInteger` is written `addInteger`.
+pub fn builtin_name(builtin: &DefaultFunction) -> String {
+    let debug = format!("{builtin:?}");
+    let mut chars = debug.chars();
+
+    match chars.next() {
+        Some(c) => c.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => debug,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigInt;
+
+    use crate::ast::{Constant, Name, Program, Term, Type};
+
+    #[test]
+    fn displays_simple_constant() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::Integer(BigInt::from(11))),
+        };
+
+        assert_eq!(program.to_string(), "(program 1.0.0 (con integer 11))");
+    }
+
+    #[test]
+    fn displays_error() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Error,
+        };
+
+        assert_eq!(program.to_string(), "(program 1.0.0 (error))");
+    }
+
+    #[test]
+    fn displays_list_of_integers() {
+        let program = Program::<Name> {
+            version: (1, 0, 0),
+            term: Term::Constant(Constant::ProtoList(
+                Type::Integer,
+                vec![Constant::Integer(BigInt::from(1)), Constant::Integer(BigInt::from(2))],
+            )),
+        };
+
+        assert_eq!(
+            program.to_string(),
+            "(program 1.0.0 (con (list integer) [1, 2]))"
+        );
+    }
+}